@@ -1,14 +1,89 @@
 use bevy_ecs::prelude::*;
-use engine_core::{math::Vec2, Color};
+use engine_core::{math::{Mat4, Vec2, Vec3}, Color};
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 #[derive(Component, Debug)]
 pub struct Transform {
     pub position: Vec2,
     pub scale: Vec2,
     pub rotation: f32,
+    /// 描画レイヤー。値が小さいほど手前に描かれる (深度テストはLessEqual)。
+    pub z: f32,
 }
 
 #[derive(Component, Debug)]
 pub struct Renderable {
     pub color: Color,
+}
+
+/// 2Dカメラ。ワールド座標を正射影でクリップ空間へ写す。
+/// `viewport`が変わってもジオメトリが歪まないよう、射影はアスペクト比を考慮して組む。
+#[derive(Resource, Debug)]
+pub struct Camera2D {
+    pub position: Vec2,
+    pub zoom: f32,
+    pub viewport: Vec2,
+}
+
+impl Camera2D {
+    /// ビュー射影行列 (`ortho * inverse(view)`) を構築する。
+    pub fn view_proj(&self) -> Mat4 {
+        let half = self.viewport * 0.5 / self.zoom.max(f32::EPSILON);
+        let proj = Mat4::orthographic_lh(-half.x, half.x, -half.y, half.y, -1.0, 1.0);
+        let view = Mat4::from_translation(Vec3::new(self.position.x, self.position.y, 0.0)).inverse();
+        proj * view
+    }
+}
+
+impl Default for Camera2D {
+    fn default() -> Self {
+        Self {
+            position: Vec2::ZERO,
+            zoom: 1.0,
+            // 高さ2.0のワールド領域。従来のNDC [-1,1] ビューと一致する既定値。
+            viewport: Vec2::new(2.0, 2.0),
+        }
+    }
+}
+
+/// レンダラが管理するテクスチャへのハンドル。
+pub type TextureHandle = usize;
+
+/// テクスチャ付きスプライト。`handle`が指すテクスチャを、
+/// `uv_offset`/`uv_scale`で切り出したサブ矩形 (スプライトシートのアトラス) で描画する。
+#[derive(Component, Debug)]
+pub struct Sprite {
+    pub handle: TextureHandle,
+    pub uv_offset: Vec2,
+    pub uv_scale: Vec2,
+}
+
+impl Sprite {
+    /// テクスチャ全体を使うスプライトを生成する。
+    pub fn new(handle: TextureHandle) -> Self {
+        Self {
+            handle,
+            uv_offset: Vec2::ZERO,
+            uv_scale: Vec2::ONE,
+        }
+    }
+}
+
+/// パス→テクスチャハンドルの対応を保持し、同じ画像の多重アップロードを防ぐ。
+#[derive(Resource, Debug, Default)]
+pub struct TextureCache {
+    handles: HashMap<PathBuf, TextureHandle>,
+}
+
+impl TextureCache {
+    /// 読み込み済みならハンドルを返す。
+    pub fn get(&self, path: &PathBuf) -> Option<TextureHandle> {
+        self.handles.get(path).copied()
+    }
+
+    /// パスとハンドルの対応を登録する。
+    pub fn insert(&mut self, path: PathBuf, handle: TextureHandle) {
+        self.handles.insert(path, handle);
+    }
 }
\ No newline at end of file
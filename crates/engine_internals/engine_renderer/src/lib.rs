@@ -1,5 +1,9 @@
 pub mod instance;
 pub mod pipeline;
+pub mod post;
 pub mod renderer;
+pub mod texture;
 
+pub use post::PostProcessChain;
 pub use renderer::{RenderError, Renderer};
+pub use texture::Texture;
@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::instance::InstanceRaw;
+
+/// z順でスプライトを重ねるための深度バッファのフォーマット。
+pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// `#include "relative/path.wgsl"` を展開しながらWGSLを読み込み、シェーダモジュールを返す。
+/// 同じファイルが複数回includeされても一度だけ展開し、循環includeは明確なメッセージで失敗する。
+pub fn load_wgsl(device: &wgpu::Device, root_path: impl AsRef<Path>) -> wgpu::ShaderModule {
+    let root = root_path.as_ref();
+    let source = preprocess_wgsl(root)
+        .unwrap_or_else(|e| panic!("Failed to preprocess WGSL {:?}: {}", root, e));
+
+    device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: root.to_str(),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    })
+}
+
+/// `#include`を展開した最終的なWGSLソース文字列を返す。ホットリロードのように
+/// `ShaderModule`生成前にソースへ手を加えたい経路から使う。
+pub fn preprocess_wgsl(root_path: impl AsRef<Path>) -> Result<String, String> {
+    let mut included = HashSet::new();
+    let mut stack = Vec::new();
+    preprocess(root_path.as_ref(), &mut included, &mut stack)
+}
+
+// 1ファイルを再帰的に展開する。`stack`は現在展開中のファイル列で循環検出に使う。
+fn preprocess(
+    path: &Path,
+    included: &mut HashSet<PathBuf>,
+    stack: &mut Vec<PathBuf>,
+) -> Result<String, String> {
+    let canonical = path
+        .canonicalize()
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    if stack.contains(&canonical) {
+        return Err(format!("cyclic #include detected at {}", canonical.display()));
+    }
+    // 二重includeは先頭の1回だけ展開する。
+    if !included.insert(canonical.clone()) {
+        return Ok(String::new());
+    }
+
+    let content = std::fs::read_to_string(&canonical)
+        .map_err(|e| format!("{}: {}", canonical.display(), e))?;
+    let dir = canonical.parent().unwrap_or_else(|| Path::new("."));
+
+    stack.push(canonical.clone());
+    let mut out = String::new();
+    for line in content.lines() {
+        if let Some(rel) = parse_include(line) {
+            let included_path = dir.join(rel);
+            out.push_str(&preprocess(&included_path, included, stack)?);
+        } else {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    stack.pop();
+    Ok(out)
+}
+
+// `#include "path"` 行からインクルード先の相対パスを取り出す。
+fn parse_include(line: &str) -> Option<&str> {
+    let rest = line.trim().strip_prefix("#include")?;
+    let rest = rest.trim();
+    rest.strip_prefix('"')?.strip_suffix('"')
+}
+
+// 頂点バッファ (quadの位置 + UV) + インスタンスバッファのレイアウトで描画パイプラインを組む。
+pub fn create_render_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Render Pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_main"),
+            buffers: &[
+                wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<[f32; 5]>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x2],
+                },
+                InstanceRaw::desc(),
+            ],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::REPLACE),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::LessEqual,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    })
+}
@@ -0,0 +1,297 @@
+//! オフスクリーンレンダリングと、全画面ポストプロセスパスのチェーン。
+//!
+//! シーンを中間カラーテクスチャへ描き、登録された`PostEffect`群をping-pong
+//! バッファ上で順に適用し、最終パスがサーフェスビューへ書き出す。
+//! チェーンが空のときもpassthroughパスがシーンをそのままサーフェスへ転送する。
+
+// 各エフェクトは共通の全画面頂点シェーダの後ろにユーザ提供のフラグメント本体を連結して使う。
+const FULLSCREEN_VS: &str = r#"
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+struct PostUniform {
+    resolution: vec2<f32>,
+    time: f32,
+    _pad: f32,
+};
+
+@group(0) @binding(0) var t_input: texture_2d<f32>;
+@group(0) @binding(1) var s_input: sampler;
+@group(0) @binding(2) var<uniform> post: PostUniform;
+
+@vertex
+fn vs_main(@builtin(vertex_index) idx: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = f32((idx << 1u) & 2u);
+    let y = f32(idx & 2u);
+    out.uv = vec2<f32>(x, 1.0 - y);
+    out.clip_position = vec4<f32>(x * 2.0 - 1.0, 1.0 - y * 2.0, 0.0, 1.0);
+    return out;
+}
+"#;
+
+const PASSTHROUGH_FS: &str = r#"
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(t_input, s_input, in.uv);
+}
+"#;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct PostUniform {
+    resolution: [f32; 2],
+    time: f32,
+    _pad: f32,
+}
+
+fn create_view(device: &wgpu::Device, format: wgpu::TextureFormat, width: u32, height: u32) -> wgpu::TextureView {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("PostProcess Target"),
+        size: wgpu::Extent3d {
+            width: width.max(1),
+            height: height.max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+/// チェーン中の1エフェクト。専用パイプラインとtime/resolutionユニフォームを持つ。
+pub struct PostEffect {
+    pipeline: wgpu::RenderPipeline,
+    uniform_buffer: wgpu::Buffer,
+}
+
+/// オフスクリーンのシーンテクスチャへ順に適用される全画面エフェクトの列。
+pub struct PostProcessChain {
+    layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    format: wgpu::TextureFormat,
+    effects: Vec<PostEffect>,
+    passthrough: PostEffect,
+    ping_pong: [wgpu::TextureView; 2],
+}
+
+impl PostProcessChain {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat, width: u32, height: u32) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("PostProcess Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("PostProcess Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let passthrough = Self::build_effect(device, &layout, format, PASSTHROUGH_FS);
+
+        Self {
+            layout,
+            sampler,
+            format,
+            effects: Vec::new(),
+            passthrough,
+            ping_pong: [
+                create_view(device, format, width, height),
+                create_view(device, format, width, height),
+            ],
+        }
+    }
+
+    /// フラグメント本体を与えてエフェクトを末尾に追加する。
+    pub fn push_effect(&mut self, device: &wgpu::Device, fragment_source: &str) {
+        let effect = Self::build_effect(device, &self.layout, self.format, fragment_source);
+        self.effects.push(effect);
+    }
+
+    /// 末尾のエフェクトを取り除く。
+    pub fn pop_effect(&mut self) -> bool {
+        self.effects.pop().is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// 中間テクスチャをサーフェスサイズに合わせて再生成する。
+    pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) {
+        self.ping_pong = [
+            create_view(device, self.format, width, height),
+            create_view(device, self.format, width, height),
+        ];
+    }
+
+    fn build_effect(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        format: wgpu::TextureFormat,
+        fragment_source: &str,
+    ) -> PostEffect {
+        let source = format!("{FULLSCREEN_VS}\n{fragment_source}");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("PostProcess Shader"),
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("PostProcess Pipeline Layout"),
+            bind_group_layouts: &[layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("PostProcess Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("PostProcess Uniform"),
+            size: std::mem::size_of::<PostUniform>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        PostEffect {
+            pipeline,
+            uniform_buffer,
+        }
+    }
+
+    /// `scene_view`を入力に全エフェクトを適用し、最終結果を`surface_view`へ書き出す。
+    pub fn run(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        scene_view: &wgpu::TextureView,
+        surface_view: &wgpu::TextureView,
+        size: (u32, u32),
+        time: f32,
+    ) {
+        let uniform = PostUniform {
+            resolution: [size.0 as f32, size.1 as f32],
+            time,
+            _pad: 0.0,
+        };
+
+        if self.effects.is_empty() {
+            self.draw(device, queue, encoder, &self.passthrough, scene_view, surface_view, uniform);
+            return;
+        }
+
+        let last = self.effects.len() - 1;
+        for (i, effect) in self.effects.iter().enumerate() {
+            let input = if i == 0 {
+                scene_view
+            } else {
+                &self.ping_pong[(i - 1) % 2]
+            };
+            let output = if i == last {
+                surface_view
+            } else {
+                &self.ping_pong[i % 2]
+            };
+            self.draw(device, queue, encoder, effect, input, output, uniform);
+        }
+    }
+
+    fn draw(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        effect: &PostEffect,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+        uniform: PostUniform,
+    ) {
+        queue.write_buffer(&effect.uniform_buffer, 0, bytemuck::cast_slice(&[uniform]));
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("PostProcess Bind Group"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(input),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: effect.uniform_buffer.as_entire_binding(),
+                },
+            ],
+        });
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("PostProcess Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: output,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            ..Default::default()
+        });
+        render_pass.set_pipeline(&effect.pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
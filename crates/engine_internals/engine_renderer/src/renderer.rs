@@ -1,29 +1,167 @@
 use std::iter;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
 use wgpu::util::DeviceExt;
 
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::post::PostProcessChain;
+use crate::texture::Texture;
 use crate::{instance::InstanceRaw, pipeline};
 use engine_core::math::{Mat4, Vec3};
 use engine_ecs::{
-    components::{Renderable, Transform},
+    components::{Camera2D, Renderable, Sprite, TextureCache, TextureHandle, Transform},
     prelude::*,
 };
 
 #[derive(Debug)]
 pub enum RenderError {}
 
+// インスタンスバッファの初期容量 (InstanceRawの個数)。これを超えると2のべき乗で拡張する。
+const INITIAL_INSTANCE_CAPACITY: usize = 1024;
+
+// インスタンスバッファの拡張判定。容量が足りていればNone、足りなければ次の2のべき乗を返す。
+// GPUに触れない純粋関数なので単体テストで拡張ロジックを検証できる。
+fn grown_capacity(current: usize, required: usize) -> Option<usize> {
+    if required <= current {
+        None
+    } else {
+        Some(required.next_power_of_two())
+    }
+}
+
+/// デバッグUIのパネル。`egui::Context`へウィジェットを積みつつ`World`を読み書きできる。
+/// エンティティ一覧やTransformエディタ、フレーム時間グラフなどをシステム側から登録する。
+pub type DebugPanel = Box<dyn FnMut(&egui::Context, &mut World)>;
+
+// カメラのビュー射影行列をGPUへ渡すためのユニフォーム
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct CameraUniform {
+    view_proj: [[f32; 4]; 4],
+}
+
+// シェーダのホットリロード用の状態。ファイル変更をチャンネル経由で受け取る。
+struct ShaderWatch {
+    path: PathBuf,
+    rx: Receiver<()>,
+    _watcher: RecommendedWatcher,
+}
+
 pub struct Renderer {
     render_pipeline: wgpu::RenderPipeline,
     vertex_buffer: wgpu::Buffer,
     index_buffer: wgpu::Buffer,
     instance_buffer: wgpu::Buffer,
+    // インスタンスバッファが保持できるInstanceRawの個数。超えたら拡張する。
+    instance_capacity: usize,
     num_indices: u32,
+    // カメラのビュー射影ユニフォーム (group 0)
+    camera_buffer: wgpu::Buffer,
+    camera_bind_group: wgpu::BindGroup,
+    // パイプライン再生成に必要な情報と、ホットリロード状態。
+    pipeline_layout: wgpu::PipelineLayout,
+    surface_format: wgpu::TextureFormat,
+    shader_watch: Option<ShaderWatch>,
+    // シーンを一旦描くオフスクリーンテクスチャと、ポストプロセスチェーン。
+    scene_view: wgpu::TextureView,
+    // z順でスプライトを重ねるための深度バッファ。
+    depth_view: wgpu::TextureView,
+    scene_size: (u32, u32),
+    post_chain: PostProcessChain,
+    // スプライト用テクスチャ。ハンドル0は常にSpriteを持たない単色用の白テクスチャ。
+    texture_bind_group_layout: wgpu::BindGroupLayout,
+    textures: Vec<Texture>,
+    // 即時モードのデバッグUI。メインパス後にサーフェスへ重ねて描く。
+    egui_ctx: egui::Context,
+    egui_state: Option<egui_winit::State>,
+    egui_renderer: egui_wgpu::Renderer,
+    debug_panels: Vec<DebugPanel>,
+}
+
+// シーン描画先のオフスクリーンカラーテクスチャのビューを生成する。
+fn create_scene_view(device: &wgpu::Device, format: wgpu::TextureFormat, width: u32, height: u32) -> wgpu::TextureView {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Scene Texture"),
+        size: wgpu::Extent3d {
+            width: width.max(1),
+            height: height.max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+// サーフェスサイズに合わせた深度テクスチャのビューを生成する。
+fn create_depth_view(device: &wgpu::Device, width: u32, height: u32) -> wgpu::TextureView {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Depth Texture"),
+        size: wgpu::Extent3d {
+            width: width.max(1),
+            height: height.max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: pipeline::DEPTH_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
 }
 
 impl Renderer {
     pub fn new(device: &wgpu::Device, config: &wgpu::SurfaceConfiguration) -> Self {
-        let shader = device.create_shader_module(wgpu::include_wgsl!("shader.wgsl"));
+        // include_wgsl!ではなく#includeプリプロセッサ経由で読み込み、共通定義を共有できるようにする。
+        let shader_path = concat!(env!("CARGO_MANIFEST_DIR"), "/src/shader.wgsl");
+        let shader = pipeline::load_wgsl(device, shader_path);
+
+        // カメラユニフォーム (group 0) のバインドグループレイアウト / バッファ
+        let camera_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Camera Bind Group Layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }],
+            });
+        let camera_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Camera Buffer"),
+            size: std::mem::size_of::<CameraUniform>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let camera_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Camera Bind Group"),
+            layout: &camera_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: camera_buffer.as_entire_binding(),
+            }],
+        });
+
+        // スプライトのテクスチャ/サンプラー (group 1)
+        let texture_bind_group_layout = Texture::bind_group_layout(device);
+
         let render_pipeline_layout =
-            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor::default());
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[&camera_bind_group_layout, &texture_bind_group_layout],
+                push_constant_ranges: &[],
+            });
         let render_pipeline = pipeline::create_render_pipeline(
             device,
             &render_pipeline_layout,
@@ -31,11 +169,12 @@ impl Renderer {
             config.format,
         );
 
-        const VERTICES: &[[f32; 3]] = &[
-            [-0.5, -0.5, 0.0],
-            [0.5, -0.5, 0.0],
-            [0.5, 0.5, 0.0],
-            [-0.5, 0.5, 0.0],
+        // 位置 + UV
+        const VERTICES: &[[f32; 5]] = &[
+            [-0.5, -0.5, 0.0, 0.0, 1.0],
+            [0.5, -0.5, 0.0, 1.0, 1.0],
+            [0.5, 0.5, 0.0, 1.0, 0.0],
+            [-0.5, 0.5, 0.0, 0.0, 0.0],
         ];
         const INDICES: &[u16] = &[0, 1, 2, 0, 2, 3];
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -51,7 +190,7 @@ impl Renderer {
         let num_indices = INDICES.len() as u32;
         let instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Instance Buffer"),
-            size: (std::mem::size_of::<InstanceRaw>() * 1024) as u64,
+            size: (std::mem::size_of::<InstanceRaw>() * INITIAL_INSTANCE_CAPACITY) as u64,
             usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
             mapped_at_creation: false,
         });
@@ -61,67 +200,357 @@ impl Renderer {
             vertex_buffer,
             index_buffer,
             instance_buffer,
+            instance_capacity: INITIAL_INSTANCE_CAPACITY,
             num_indices,
+            camera_buffer,
+            camera_bind_group,
+            pipeline_layout: render_pipeline_layout,
+            surface_format: config.format,
+            shader_watch: None,
+            scene_view: create_scene_view(device, config.format, config.width, config.height),
+            depth_view: create_depth_view(device, config.width, config.height),
+            scene_size: (config.width, config.height),
+            post_chain: PostProcessChain::new(device, config.format, config.width, config.height),
+            texture_bind_group_layout,
+            textures: Vec::new(),
+            egui_ctx: egui::Context::default(),
+            egui_state: None,
+            egui_renderer: egui_wgpu::Renderer::new(device, config.format, None, 1, false),
+            debug_panels: Vec::new(),
+        }
+    }
+
+    /// デバッグUIのパネルを登録する。毎フレーム`egui::Context`と`World`を受け取り、
+    /// リソースやコンポーネントをその場で読み書きできる。
+    pub fn register_debug_panel<F>(&mut self, panel: F)
+    where
+        F: FnMut(&egui::Context, &mut World) + 'static,
+    {
+        self.debug_panels.push(Box::new(panel));
+    }
+
+    /// winitのウィンドウイベントをeguiへ渡す。UIが入力を消費した場合は`true`を返すので、
+    /// 呼び出し側はゲーム入力への転送を抑制できる。
+    pub fn input(
+        &mut self,
+        window: &winit::window::Window,
+        event: &winit::event::WindowEvent,
+    ) -> bool {
+        let ctx = self.egui_ctx.clone();
+        let state = self.egui_state.get_or_insert_with(|| {
+            egui_winit::State::new(ctx, egui::ViewportId::ROOT, window, None, None, None)
+        });
+        state.on_window_event(window, event).consumed
+    }
+
+    /// 画像を読み込み、スプライトから参照できるテクスチャハンドルを返す。
+    /// `TextureCache`を参照し、同じパスの画像は一度しかアップロードしない。
+    pub fn load_texture(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        cache: &mut TextureCache,
+        path: impl AsRef<Path>,
+    ) -> image::ImageResult<TextureHandle> {
+        self.ensure_white_texture(device, queue);
+        let path_buf = path.as_ref().to_path_buf();
+        if let Some(handle) = cache.get(&path_buf) {
+            return Ok(handle);
+        }
+        let texture =
+            Texture::from_path(device, queue, &self.texture_bind_group_layout, &path_buf)?;
+        self.textures.push(texture);
+        let handle = self.textures.len() - 1;
+        cache.insert(path_buf, handle);
+        Ok(handle)
+    }
+
+    // ハンドル0の1x1白テクスチャを必要に応じて生成する (queueはnewに無いため遅延生成)。
+    fn ensure_white_texture(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        if self.textures.is_empty() {
+            let white = Texture::from_rgba(
+                device,
+                queue,
+                &self.texture_bind_group_layout,
+                &[255, 255, 255, 255],
+                (1, 1),
+            );
+            self.textures.push(white);
+        }
+    }
+
+    /// ポストプロセスチェーンへの可変参照。`push_effect`でbloom等を登録できる。
+    pub fn post_chain_mut(&mut self) -> &mut PostProcessChain {
+        &mut self.post_chain
+    }
+
+    /// 現在のインスタンスバッファ容量 (InstanceRawの個数)。
+    pub fn instance_capacity(&self) -> usize {
+        self.instance_capacity
+    }
+
+    // 必要数がバッファ容量を超えていたら、次の2のべき乗サイズへ拡張する。
+    fn ensure_instance_capacity(&mut self, device: &wgpu::Device, required: usize) {
+        let Some(new_capacity) = grown_capacity(self.instance_capacity, required) else {
+            return;
+        };
+        self.instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Instance Buffer"),
+            size: (std::mem::size_of::<InstanceRaw>() * new_capacity) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        self.instance_capacity = new_capacity;
+    }
+
+    /// オフスクリーンテクスチャとポストプロセスの中間テクスチャをサイズ変更に追従させる。
+    pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
         }
+        self.scene_view = create_scene_view(device, self.surface_format, width, height);
+        self.depth_view = create_depth_view(device, width, height);
+        self.scene_size = (width, height);
+        self.post_chain.resize(device, width, height);
+    }
+
+    /// 指定したWGSLファイルを監視し、変更されたらパイプラインを組み直す。
+    /// `Renderer::new`は起動時にディスクから一度読み込むだけで、その後の編集は
+    /// 反映しない。こちらは変更のたびにディスクから読み直すホットリロード経路。
+    pub fn watch_shader(&mut self, path: impl AsRef<Path>) -> notify::Result<()> {
+        let path = path.as_ref().to_path_buf();
+        let (tx, rx) = channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            if res.is_ok() {
+                // 変更の種別は問わず「更新あり」だけを通知する。
+                let _ = tx.send(());
+            }
+        })?;
+        watcher.watch(&path, RecursiveMode::NonRecursive)?;
+        self.shader_watch = Some(ShaderWatch {
+            path,
+            rx,
+            _watcher: watcher,
+        });
+        Ok(())
+    }
+
+    /// 監視中のシェーダに変更があればパイプラインを再生成する。
+    /// コンパイルに失敗した場合は直前の正常なパイプラインを保持する。
+    pub fn poll_shader_reload(&mut self, device: &wgpu::Device) {
+        let Some(watch) = self.shader_watch.as_ref() else {
+            return;
+        };
+        // 溜まったイベントをまとめて1回のリロードに畳み込む。
+        let mut changed = false;
+        while watch.rx.try_recv().is_ok() {
+            changed = true;
+        }
+        if !changed {
+            return;
+        }
+
+        // `new`と同じく#includeプリプロセッサを通す。生読みだと`#include`行でnaga検証が
+        // 必ず失敗し、リロードが常に無言で捨てられてしまう。
+        let source = match pipeline::preprocess_wgsl(&watch.path) {
+            Ok(source) => source,
+            Err(e) => {
+                log::error!("Failed to preprocess shader {:?}: {}", watch.path, e);
+                return;
+            }
+        };
+
+        // バリデーションエラーを捕捉し、タイプミスでエンジンが落ちないようにする。
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Hot-reloaded Shader"),
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+        let pipeline = pipeline::create_render_pipeline(
+            device,
+            &self.pipeline_layout,
+            &shader,
+            self.surface_format,
+        );
+        if let Some(error) = pollster::block_on(device.pop_error_scope()) {
+            log::error!("Shader reload failed, keeping last known-good pipeline: {error}");
+            return;
+        }
+
+        self.render_pipeline = pipeline;
+        log::info!("Reloaded shader {:?}", watch.path);
     }
 
     pub fn render(
         &mut self,
         world: &mut World,
         view: &wgpu::TextureView,
+        window: &winit::window::Window,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
     ) -> Result<(), RenderError> {
         let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
 
-        let mut query = world.query::<(&Transform, &Renderable)>();
-        let instance_data = query
-            .iter(world)
-            .map(|(transform, renderable)| {
-                let model_matrix = Mat4::from_translation(Vec3::new(
-                    transform.position.x,
-                    transform.position.y,
-                    0.0,
-                )) * Mat4::from_rotation_z(transform.rotation)
-                    * Mat4::from_scale(Vec3::new(transform.scale.x, transform.scale.y, 1.0));
-                InstanceRaw {
-                    model: model_matrix.to_cols_array_2d(),
-                    color: [
-                        renderable.color.r,
-                        renderable.color.g,
-                        renderable.color.b,
-                        renderable.color.a,
-                    ],
+        // カメラのビュー射影を更新する。Camera2Dが無ければ従来どおり生NDCで描く。
+        let view_proj = match world.get_resource::<Camera2D>() {
+            Some(camera) => camera.view_proj(),
+            None => Mat4::IDENTITY,
+        };
+        queue.write_buffer(
+            &self.camera_buffer,
+            0,
+            bytemuck::cast_slice(&[CameraUniform {
+                view_proj: view_proj.to_cols_array_2d(),
+            }]),
+        );
+
+        // 単色描画でも白テクスチャが要るので、ハンドル0を用意しておく。
+        self.ensure_white_texture(device, queue);
+
+        // テクスチャごとにインスタンスをまとめ、バインドグループの切り替え回数を最小化する。
+        // Spriteを持たないエンティティはハンドル0 (白テクスチャ) のグループに入り、単色描画になる。
+        let mut query = world.query::<(&Transform, &Renderable, Option<&Sprite>)>();
+        let mut batches: std::collections::BTreeMap<TextureHandle, Vec<InstanceRaw>> =
+            std::collections::BTreeMap::new();
+        for (transform, renderable, sprite) in query.iter(world) {
+            let model_matrix = Mat4::from_translation(Vec3::new(
+                transform.position.x,
+                transform.position.y,
+                transform.z,
+            )) * Mat4::from_rotation_z(transform.rotation)
+                * Mat4::from_scale(Vec3::new(transform.scale.x, transform.scale.y, 1.0));
+
+            let (handle, uv_offset, uv_scale) = match sprite {
+                Some(s) if s.handle < self.textures.len() => {
+                    (s.handle, [s.uv_offset.x, s.uv_offset.y], [s.uv_scale.x, s.uv_scale.y])
                 }
-            })
-            .collect::<Vec<_>>();
+                // Spriteが無い / ハンドルが無効な場合は白テクスチャ全面を使う。
+                _ => (0, [0.0, 0.0], [1.0, 1.0]),
+            };
+
+            batches.entry(handle).or_default().push(InstanceRaw {
+                model: model_matrix.to_cols_array_2d(),
+                color: [
+                    renderable.color.r,
+                    renderable.color.g,
+                    renderable.color.b,
+                    renderable.color.a,
+                ],
+                uv_offset,
+                uv_scale,
+            });
+        }
+
+        // インスタンスをグループ順に連続配置し、各テクスチャの描画範囲を記録する。
+        let mut instance_data = Vec::new();
+        let mut draw_ranges: Vec<(TextureHandle, std::ops::Range<u32>)> = Vec::new();
+        for (handle, instances) in &batches {
+            let start = instance_data.len() as u32;
+            instance_data.extend_from_slice(instances);
+            draw_ranges.push((*handle, start..instance_data.len() as u32));
+        }
 
+        // 1024を超えるエンティティでもバッファを溢れさせないよう、必要なら拡張してから書き込む。
+        self.ensure_instance_capacity(device, instance_data.len());
         queue.write_buffer(
             &self.instance_buffer,
             0,
             bytemuck::cast_slice(&instance_data),
         );
 
+        // 常にオフスクリーンのシーンテクスチャへ描き、チェーンが空でもpassthroughで
+        // サーフェスへ転送する。
         {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Render Pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view,
+                    view: &self.scene_view,
                     resolve_target: None,
                     ops: wgpu::Operations {
                         load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
                         store: wgpu::StoreOp::Store,
                     },
                 })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
                 ..Default::default()
             });
 
             render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
             render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
             render_pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
             render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
-            if !instance_data.is_empty() {
-                render_pass.draw_indexed(0..self.num_indices, 0, 0..instance_data.len() as u32);
+
+            // テクスチャごとに1回のdraw_indexedへまとめる。
+            for (handle, range) in &draw_ranges {
+                render_pass.set_bind_group(1, &self.textures[*handle].bind_group, &[]);
+                render_pass.draw_indexed(0..self.num_indices, 0, range.clone());
+            }
+        }
+
+        // ポストプロセスを適用し、最終結果をサーフェスビューへ書き出す。
+        // チェーンが空でもrun内のpassthroughがシーンをそのまま転送する。
+        self.post_chain.run(
+            device,
+            queue,
+            &mut encoder,
+            &self.scene_view,
+            view,
+            self.scene_size,
+            0.0,
+        );
+
+        // デバッグUIをメインパス後にサーフェスビューへ重ねる (LoadOp::Loadでシーンを残す)。
+        {
+            let ctx = self.egui_ctx.clone();
+            let state = self.egui_state.get_or_insert_with(|| {
+                egui_winit::State::new(ctx.clone(), egui::ViewportId::ROOT, window, None, None, None)
+            });
+            let raw_input = state.take_egui_input(window);
+            let panels = &mut self.debug_panels;
+            let full_output = ctx.run(raw_input, |ctx| {
+                for panel in panels.iter_mut() {
+                    panel(ctx, world);
+                }
+            });
+            state.handle_platform_output(window, full_output.platform_output);
+
+            let paint_jobs = ctx.tessellate(full_output.shapes, full_output.pixels_per_point);
+            let screen = egui_wgpu::ScreenDescriptor {
+                size_in_pixels: [self.scene_size.0, self.scene_size.1],
+                pixels_per_point: full_output.pixels_per_point,
+            };
+            for (id, delta) in &full_output.textures_delta.set {
+                self.egui_renderer.update_texture(device, queue, *id, delta);
+            }
+            self.egui_renderer
+                .update_buffers(device, queue, &mut encoder, &paint_jobs, &screen);
+            {
+                let mut render_pass = encoder
+                    .begin_render_pass(&wgpu::RenderPassDescriptor {
+                        label: Some("egui Pass"),
+                        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                            view,
+                            resolve_target: None,
+                            ops: wgpu::Operations {
+                                load: wgpu::LoadOp::Load,
+                                store: wgpu::StoreOp::Store,
+                            },
+                        })],
+                        ..Default::default()
+                    })
+                    .forget_lifetime();
+                self.egui_renderer.render(&mut render_pass, &paint_jobs, &screen);
+            }
+            for id in &full_output.textures_delta.free {
+                self.egui_renderer.free_texture(id);
             }
         }
 
@@ -129,3 +558,20 @@ impl Renderer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{grown_capacity, INITIAL_INSTANCE_CAPACITY};
+
+    #[test]
+    fn within_capacity_does_not_grow() {
+        assert_eq!(grown_capacity(INITIAL_INSTANCE_CAPACITY, INITIAL_INSTANCE_CAPACITY), None);
+    }
+
+    #[test]
+    fn exceeding_capacity_grows_to_next_power_of_two() {
+        // 1024を超えるインスタンスを積むと次の2のべき乗 (2048) へ拡張する。
+        assert_eq!(grown_capacity(INITIAL_INSTANCE_CAPACITY, 1025), Some(2048));
+        assert_eq!(grown_capacity(INITIAL_INSTANCE_CAPACITY, 5000), Some(8192));
+    }
+}
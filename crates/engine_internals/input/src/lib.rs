@@ -1,5 +1,7 @@
 use bevy_ecs::prelude::*;
-use std::collections::HashSet;
+use engine_core::math::Vec2;
+use std::collections::{HashMap, HashSet};
+use winit::{event::MouseButton, keyboard::KeyCode};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum InputAction {
@@ -9,21 +11,142 @@ pub enum InputAction {
     MoveRight,
 }
 
-#[derive(Resource, Debug, Default, Clone)]
+#[derive(Resource, Debug, Clone)]
 pub struct InputManager {
+    // キー→アクションの割り当て (ハードコードではなく設定可能)。
+    bindings: HashMap<KeyCode, InputAction>,
     pressed_actions: HashSet<InputAction>,
+    // このフレームで押された/離されたアクション (保持状態とは区別するエッジ)。
+    just_pressed_actions: HashSet<InputAction>,
+    just_released_actions: HashSet<InputAction>,
+    // マウス状態。
+    cursor_position: Vec2,
+    pressed_buttons: HashSet<MouseButton>,
+    just_pressed_buttons: HashSet<MouseButton>,
+    just_released_buttons: HashSet<MouseButton>,
+    scroll_delta: f32,
 }
 
 impl InputManager {
+    /// キーにアクションを割り当てる。
+    pub fn bind(&mut self, key: KeyCode, action: InputAction) {
+        self.bindings.insert(key, action);
+    }
+
+    /// 物理キーの押下/解放を受け取り、割り当て済みアクションの状態を更新する。
+    pub fn handle_key(&mut self, key: KeyCode, pressed: bool) {
+        if let Some(&action) = self.bindings.get(&key) {
+            if pressed {
+                self.action_pressed(action);
+            } else {
+                self.action_released(action);
+            }
+        }
+    }
+
     pub fn action_pressed(&mut self, key: InputAction) {
-        self.pressed_actions.insert(key);
+        // 既に保持中なら連続入力なのでjust_pressedには積まない。
+        if self.pressed_actions.insert(key) {
+            self.just_pressed_actions.insert(key);
+        }
     }
 
     pub fn action_released(&mut self, key: InputAction) {
-        self.pressed_actions.remove(&key);
+        if self.pressed_actions.remove(&key) {
+            self.just_released_actions.insert(key);
+        }
     }
 
     pub fn is_action_pressed(&self, key: &InputAction) -> bool {
         self.pressed_actions.contains(key)
     }
+
+    pub fn is_action_just_pressed(&self, key: &InputAction) -> bool {
+        self.just_pressed_actions.contains(key)
+    }
+
+    pub fn is_action_just_released(&self, key: &InputAction) -> bool {
+        self.just_released_actions.contains(key)
+    }
+
+    // --- マウス ---
+
+    pub fn set_cursor_position(&mut self, position: Vec2) {
+        self.cursor_position = position;
+    }
+
+    pub fn cursor_position(&self) -> Vec2 {
+        self.cursor_position
+    }
+
+    pub fn handle_mouse_button(&mut self, button: MouseButton, pressed: bool) {
+        if pressed {
+            if self.pressed_buttons.insert(button) {
+                self.just_pressed_buttons.insert(button);
+            }
+        } else if self.pressed_buttons.remove(&button) {
+            self.just_released_buttons.insert(button);
+        }
+    }
+
+    pub fn is_button_pressed(&self, button: MouseButton) -> bool {
+        self.pressed_buttons.contains(&button)
+    }
+
+    pub fn is_button_just_pressed(&self, button: MouseButton) -> bool {
+        self.just_pressed_buttons.contains(&button)
+    }
+
+    pub fn is_button_just_released(&self, button: MouseButton) -> bool {
+        self.just_released_buttons.contains(&button)
+    }
+
+    /// スクロール量を積算する (1フレーム分)。
+    pub fn add_scroll_delta(&mut self, delta: f32) {
+        self.scroll_delta += delta;
+    }
+
+    pub fn scroll_delta(&self) -> f32 {
+        self.scroll_delta
+    }
+
+    /// フレーム終端で呼び、エッジ状態とスクロール量をリセットする。
+    pub fn clear_frame_state(&mut self) {
+        self.just_pressed_actions.clear();
+        self.just_released_actions.clear();
+        self.just_pressed_buttons.clear();
+        self.just_released_buttons.clear();
+        self.scroll_delta = 0.0;
+    }
+}
+
+impl Default for InputManager {
+    fn default() -> Self {
+        // 既定の移動バインド (WASD / 矢印キー)。利用側で上書き可能。
+        let mut bindings = HashMap::new();
+        for (key, action) in [
+            (KeyCode::KeyW, InputAction::MoveForward),
+            (KeyCode::ArrowUp, InputAction::MoveForward),
+            (KeyCode::KeyS, InputAction::MoveBack),
+            (KeyCode::ArrowDown, InputAction::MoveBack),
+            (KeyCode::KeyA, InputAction::MoveLeft),
+            (KeyCode::ArrowLeft, InputAction::MoveLeft),
+            (KeyCode::KeyD, InputAction::MoveRight),
+            (KeyCode::ArrowRight, InputAction::MoveRight),
+        ] {
+            bindings.insert(key, action);
+        }
+
+        Self {
+            bindings,
+            pressed_actions: HashSet::new(),
+            just_pressed_actions: HashSet::new(),
+            just_released_actions: HashSet::new(),
+            cursor_position: Vec2::ZERO,
+            pressed_buttons: HashSet::new(),
+            just_pressed_buttons: HashSet::new(),
+            just_released_buttons: HashSet::new(),
+            scroll_delta: 0.0,
+        }
+    }
 }
@@ -1,221 +0,0 @@
-// crates/engine_renderer/src/renderer.rs
-
-use std::{iter, sync::Arc};
-use wgpu::util::DeviceExt;
-use winit::{event::WindowEvent, window::Window};
-
-use engine_core::math::{Mat4, Vec3};
-use engine_ecs::{
-    components::{Renderable, Transform},
-    prelude::*,
-};
-
-use crate::instance::InstanceRaw;
-use crate::pipeline;
-
-// Rendererが外部に返すための公開エラー型
-#[derive(Debug)]
-pub enum RenderError {
-    SurfaceLost,
-    OutOfMemory,
-}
-
-pub struct Renderer {
-    surface: wgpu::Surface<'static>,
-    device: wgpu::Device,
-    queue: wgpu::Queue,
-    config: wgpu::SurfaceConfiguration,
-    pub size: winit::dpi::PhysicalSize<u32>,
-    render_pipeline: wgpu::RenderPipeline,
-    vertex_buffer: wgpu::Buffer,
-    index_buffer: wgpu::Buffer,
-    instance_buffer: wgpu::Buffer,
-    num_indices: u32,
-}
-
-impl Renderer {
-    pub async fn new(window: Arc<Window>) -> Self {
-        let size = window.inner_size();
-
-        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
-        let surface = instance.create_surface(window).unwrap();
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::default(),
-                compatible_surface: Some(&surface),
-                force_fallback_adapter: false,
-            })
-            .await
-            .unwrap();
-
-        let (device, queue) = adapter
-            .request_device(
-                &wgpu::DeviceDescriptor {
-                    label: None,
-                    required_features: wgpu::Features::empty(),
-                    required_limits: wgpu::Limits::default(),
-                    memory_hints: wgpu::MemoryHints::Performance,
-                    trace: wgpu::Trace::default(), // 修正
-                },
-                //第2引数Noneを削除
-            )
-            .await
-            .unwrap();
-
-        let surface_caps = surface.get_capabilities(&adapter);
-        let surface_format = surface_caps
-            .formats
-            .iter()
-            .copied()
-            .find(|f| f.is_srgb())
-            .unwrap_or(surface_caps.formats[0]);
-
-        let config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format: surface_format,
-            width: size.width,
-            height: size.height,
-            present_mode: surface_caps.present_modes[0],
-            alpha_mode: surface_caps.alpha_modes[0],
-            view_formats: vec![],
-            desired_maximum_frame_latency: 2,
-        };
-        surface.configure(&device, &config);
-
-        //【修正】シェーダーパスはcrateのsrcルートからの相対パス
-        let shader = device.create_shader_module(wgpu::include_wgsl!("shader.wgsl"));
-
-        let render_pipeline_layout =
-            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor::default());
-
-        let render_pipeline = pipeline::create_render_pipeline(
-            &device,
-            &render_pipeline_layout,
-            &shader,
-            config.format,
-        );
-
-        // --- バッファ作成処理 (変更なし) ---
-        const VERTICES: &[[f32; 3]] = &[
-            [-0.5, -0.5, 0.0],
-            [0.5, -0.5, 0.0],
-            [0.5, 0.5, 0.0],
-            [-0.5, 0.5, 0.0],
-        ];
-        const INDICES: &[u16] = &[0, 1, 2, 0, 2, 3];
-        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Vertex Buffer"),
-            contents: bytemuck::cast_slice(VERTICES),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Index Buffer"),
-            contents: bytemuck::cast_slice(INDICES),
-            usage: wgpu::BufferUsages::INDEX,
-        });
-        let num_indices = INDICES.len() as u32;
-
-        let instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Instance Buffer"),
-            size: (std::mem::size_of::<InstanceRaw>() * 1024) as u64,
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-
-        Self {
-            surface,
-            device,
-            queue,
-            config,
-            size,
-            render_pipeline,
-            vertex_buffer,
-            index_buffer,
-            instance_buffer,
-            num_indices,
-        }
-    }
-
-    // resize, input, render メソッド (変更なし)
-    pub fn resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>) {
-        if new_size.width > 0 && new_size.height > 0 {
-            self.size = new_size;
-            self.config.width = new_size.width;
-            self.config.height = new_size.height;
-            self.surface.configure(&self.device, &self.config);
-        }
-    }
-
-    pub fn input(&mut self, _event: &WindowEvent) -> bool {
-        false
-    }
-
-    pub fn render(&mut self, world: &mut World) -> Result<(), RenderError> {
-        let output = match self.surface.get_current_texture() {
-            Ok(output) => output,
-            Err(wgpu::SurfaceError::Lost) => return Err(RenderError::SurfaceLost),
-            Err(wgpu::SurfaceError::OutOfMemory) => return Err(RenderError::OutOfMemory),
-            Err(e) => panic!("Unhandled wgpu surface error: {:?}", e),
-        };
-        let view = output
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
-        let mut encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
-
-        let mut query = world.query::<(&Transform, &Renderable)>();
-        let instance_data = query
-            .iter(world)
-            .map(|(transform, renderable)| {
-                let model_matrix = Mat4::from_translation(Vec3::new(
-                    transform.position.x,
-                    transform.position.y,
-                    0.0,
-                )) * Mat4::from_rotation_z(transform.rotation)
-                    * Mat4::from_scale(Vec3::new(transform.scale.x, transform.scale.y, 1.0));
-
-                InstanceRaw {
-                    model: model_matrix.to_cols_array_2d(),
-                    color: [
-                        renderable.color.r,
-                        renderable.color.g,
-                        renderable.color.b,
-                        renderable.color.a,
-                    ],
-                }
-            })
-            .collect::<Vec<_>>();
-
-        self.queue.write_buffer(
-            &self.instance_buffer,
-            0,
-            bytemuck::cast_slice(&instance_data),
-        );
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                ..Default::default()
-            });
-
-            render_pass.set_pipeline(&self.render_pipeline);
-            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-            render_pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
-            render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
-            render_pass.draw_indexed(0..self.num_indices, 0, 0..instance_data.len() as u32);
-        }
-
-        self.queue.submit(iter::once(encoder.finish()));
-        output.present();
-
-        Ok(())
-    }
-}
@@ -4,13 +4,13 @@ use engine_ecs::{
     prelude::*,
     systems::player_movement_system,
 };
-use engine_input::{InputAction, InputManager};
+use engine_input::InputManager;
 use engine_renderer::Renderer;
 use engine_time::Time;
 use std::{sync::Arc, time::Instant};
 use winit::{
     application::ApplicationHandler,
-    event::{ElementState, KeyEvent, WindowEvent},
+    event::{ElementState, KeyEvent, MouseScrollDelta, WindowEvent},
     event_loop::{ActiveEventLoop, EventLoop},
     keyboard::{KeyCode, PhysicalKey},
     window::{Window, WindowId},
@@ -83,11 +83,17 @@ impl AppState {
     }
 }
 
+// 固定タイムステップの刻み幅 (物理/移動を60Hzで決定論的に進める)。
+const FIXED_TIMESTEP: f32 = 1.0 / 60.0;
+
 #[derive(Default)]
 struct App {
     state: Option<AppState>,
     world: World,
     input_manager: InputManager,
+    // 前フレームの時刻と、固定ステップ用の余り時間。
+    last_frame: Option<Instant>,
+    accumulator: f32,
 }
 
 impl ApplicationHandler for App {
@@ -105,6 +111,10 @@ impl ApplicationHandler for App {
 
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
         if let Some(state) = self.state.as_mut() {
+            // まずデバッグUIへイベントを渡す。UIが消費したらゲーム入力へは転送しない。
+            if state.renderer.input(&state.window, &event) {
+                return;
+            }
             match event {
                 WindowEvent::KeyboardInput {
                     event:
@@ -115,20 +125,24 @@ impl ApplicationHandler for App {
                         },
                     ..
                 } => {
-                    let action = match keycode {
-                        KeyCode::KeyW | KeyCode::ArrowUp => Some(InputAction::MoveForward),
-                        KeyCode::KeyS | KeyCode::ArrowDown => Some(InputAction::MoveBack),
-                        KeyCode::KeyA | KeyCode::ArrowLeft => Some(InputAction::MoveLeft),
-                        KeyCode::KeyD | KeyCode::ArrowRight => Some(InputAction::MoveRight),
-                        _ => None,
+                    // バインドマップ経由でキーをアクションへ解決する。
+                    self.input_manager
+                        .handle_key(keycode, state == ElementState::Pressed);
+                }
+                WindowEvent::CursorMoved { position, .. } => {
+                    self.input_manager
+                        .set_cursor_position(vec2(position.x as f32, position.y as f32));
+                }
+                WindowEvent::MouseInput { state, button, .. } => {
+                    self.input_manager
+                        .handle_mouse_button(button, state == ElementState::Pressed);
+                }
+                WindowEvent::MouseWheel { delta, .. } => {
+                    let scroll = match delta {
+                        MouseScrollDelta::LineDelta(_, y) => y,
+                        MouseScrollDelta::PixelDelta(pos) => pos.y as f32,
                     };
-
-                    if let Some(action) = action {
-                        match state {
-                            ElementState::Pressed => self.input_manager.action_pressed(action),
-                            ElementState::Released => self.input_manager.action_released(action),
-                        }
-                    }
+                    self.input_manager.add_scroll_delta(scroll);
                 }
                 WindowEvent::CloseRequested => {
                     log::info!("The close button was pressed; stopping");
@@ -138,10 +152,28 @@ impl ApplicationHandler for App {
                     state.resize(physical_size);
                 }
                 WindowEvent::RedrawRequested => {
+                    // 実フレーム時間を計測し、固定ステップのアキュムレータへ積む。
+                    let now = Instant::now();
+                    let frame_delta = match self.last_frame {
+                        Some(previous) => now.duration_since(previous).as_secs_f32(),
+                        None => 0.0,
+                    };
+                    self.last_frame = Some(now);
+                    // 一時停止などでdeltaが跳ねても破綻しないよう上限を設ける。
+                    self.accumulator += frame_delta.min(0.25);
+
                     self.world.insert_resource(self.input_manager.clone());
-                    let mut schedule = Schedule::default();
-                    schedule.add_systems(player_movement_system);
-                    schedule.run(&mut self.world);
+
+                    // FixedUpdate: 一定のdelta_secondsでN回実行し、vsyncから独立させる。
+                    let mut fixed_schedule = Schedule::default();
+                    fixed_schedule.add_systems(player_movement_system);
+                    while self.accumulator >= FIXED_TIMESTEP {
+                        if let Some(mut time) = self.world.get_resource_mut::<Time>() {
+                            time.advance_by(std::time::Duration::from_secs_f32(FIXED_TIMESTEP));
+                        }
+                        fixed_schedule.run(&mut self.world);
+                        self.accumulator -= FIXED_TIMESTEP;
+                    }
 
                     match state.surface.get_current_texture() {
                         Ok(output) => {
@@ -150,7 +182,13 @@ impl ApplicationHandler for App {
                                 .create_view(&wgpu::TextureViewDescriptor::default());
                             state
                                 .renderer
-                                .render(&mut self.world, &view, &state.device, &state.queue)
+                                .render(
+                                    &mut self.world,
+                                    &view,
+                                    &state.window,
+                                    &state.device,
+                                    &state.queue,
+                                )
                                 .unwrap();
                             output.present();
                         }
@@ -158,6 +196,9 @@ impl ApplicationHandler for App {
                         Err(wgpu::SurfaceError::OutOfMemory) => event_loop.exit(),
                         Err(e) => eprintln!("Error acquiring frame: {:?}", e),
                     }
+
+                    // フレーム終端でエッジ/スクロール状態をリセットする。
+                    self.input_manager.clear_frame_state();
                 }
                 _ => {}
             }
@@ -185,6 +226,7 @@ fn main() {
             position: vec2(0.0, 0.0),
             scale: vec2(0.2, 0.2),
             rotation: 0.0,
+            z: 0.0,
         },
         Renderable {
             color: Color::GREEN,
@@ -195,6 +237,7 @@ fn main() {
             position: vec2(0.7, 0.7),
             scale: vec2(0.2, 0.2),
             rotation: 0.0,
+            z: 0.1,
         },
         Renderable { color: Color::BLUE },
     ));
@@ -203,6 +246,8 @@ fn main() {
         state: None,
         world,
         input_manager: InputManager::default(),
+        last_frame: None,
+        accumulator: 0.0,
     };
 
     event_loop.run_app(&mut app).unwrap();